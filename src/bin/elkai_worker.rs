@@ -0,0 +1,19 @@
+//! Helper binary dispatched by [`elkai_rs::solve_batch`] to run a single
+//! solve in its own process. Reads a `\0`-terminated parameters string
+//! followed by a `\0`-terminated problem string from stdin (the same
+//! format [`elkai_rs::__solve_raw`] expects), and writes the resulting
+//! 0-based tour indices, space-separated, to stdout.
+
+use std::io::{Read, Write};
+
+fn main() {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).expect("failed to read stdin");
+
+    let split = input.find('\0').expect("missing parameters terminator") + 1;
+    let (param, problem) = input.split_at(split);
+
+    let tour = elkai_rs::__solve_raw(param, problem);
+    let tour = tour.iter().map(usize::to_string).collect::<Vec<_>>().join(" ");
+    std::io::stdout().write_all(tour.as_bytes()).expect("failed to write stdout");
+}
@@ -105,6 +105,15 @@ num_trait_impl!(Num for usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32
 impl<T: num_traits::Num + std::fmt::Display> Num for std::num::Wrapping<T>
 where std::num::Wrapping<T>: num_traits::NumOps {}
 
+/// A structured TSP solution: the ordered tour, its total closed-loop
+/// length, and the number of solver runs used to find it.
+#[derive(Debug, Clone)]
+pub struct Solution<O, L> {
+    pub tour: Vec<O>,
+    pub length: L,
+    pub runs: usize
+}
+
 /// A structure representing a matrix of float/int weights/distances.
 /// ## Example usage
 /// 
@@ -135,28 +144,69 @@ impl<T: Num> DistanceMatrix<T> {
 
     /// Returns a list of indices that represent the TSP tour. You can adjust solver iterations with the runs parameter.
     pub fn solve(&self, runs: usize) -> Vec<usize> {
-        assert!(runs >= 1, "runs must be a positive integer");
-        let dimension = self.distances.len();
-        assert!(dimension >= 3, "dimension must be at least 3");
-        let param = format!("RUNS = {runs}\nPROBLEM_FILE = :stdin:\n\0");
-        let problem_type = if is_symmetric_matrix(&self.distances) {"TSP"} else {"ATSP"};
-        let mut problem = format!("TYPE : {problem_type}\nDIMENSION : {dimension}\nEDGE_WEIGHT_TYPE : EXPLICIT\nEDGE_WEIGHT_FORMAT : FULL_MATRIX\nEDGE_WEIGHT_SECTION\n");
-        for row in &self.distances {
-            problem.push_str(&row.iter().map(T::to_string).collect::<Vec<_>>().join(" "));
-            problem.push('\n');
+        let (param, problem) = self.to_problem(runs);
+        self.tour_from_indices(elkai_solve_problem(&param, &problem))
+    }
+}
+
+impl<T: Num + Copy + std::iter::Sum> DistanceMatrix<T> {
+    /// Returns the TSP tour together with its total closed-loop length and
+    /// the number of runs used, so callers don't have to re-implement cost
+    /// computation themselves. You can adjust solver iterations with the
+    /// runs parameter.
+    pub fn solve_with_cost(&self, runs: usize) -> Solution<usize, T> {
+        let tour = self.solve(runs);
+        let length = (1..tour.len()).map(|i| self.distances[tour[i - 1]][tour[i]]).sum::<T>()
+            + self.distances[*tour.last().unwrap()][tour[0]];
+        Solution { tour, length, runs }
+    }
+}
+
+/// The TSPLIB edge-weight metric used to interpret a [`Coordinates2D`]
+/// instance. `Euc2D` (the default) matches LKH's usual rounded Euclidean
+/// distance, `Ceil2D` rounds distances up instead of to the nearest
+/// integer, and `Att` is the pseudo-Euclidean metric used by the TSPLIB
+/// `att*` instances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceMetric {
+    Euc2D,
+    Ceil2D,
+    Att
+}
+
+impl DistanceMetric {
+    fn tsplib_name(&self) -> &'static str {
+        match self {
+            DistanceMetric::Euc2D => "EUC_2D",
+            DistanceMetric::Ceil2D => "CEIL_2D",
+            DistanceMetric::Att => "ATT"
+        }
+    }
+
+    /// Computes the distance between two points for this metric. `Ceil2D`
+    /// and `Att` use LKH's integer rounding; `Euc2D` returns the exact,
+    /// unrounded Euclidean distance.
+    fn distance(&self, a: (f64, f64), b: (f64, f64)) -> f64 {
+        let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+        match self {
+            DistanceMetric::Euc2D => (dx * dx + dy * dy).sqrt(),
+            DistanceMetric::Ceil2D => (dx * dx + dy * dy).sqrt().ceil(),
+            DistanceMetric::Att => {
+                let rij = ((dx * dx + dy * dy) / 10.0).sqrt();
+                let tij = rij.round();
+                if tij < rij { tij + 1.0 } else { tij }
+            }
         }
-        problem.push('\0');
-        elkai_solve_problem(&param, &problem)
     }
 }
 
 /// A structure representing coordinates of type {'city name': (x, y), ...}
 /// ## Example usage
-///  
+///
 ///  ```rust
 ///  use std::collections::HashMap;
 ///  use elkai_rs::Coordinates2D;
-///  
+///
 ///  fn main() {
 ///      let cities = Coordinates2D::new(HashMap::from_iter([
 ///          ("city1", (0.0, 0.0)),
@@ -167,31 +217,186 @@ impl<T: Num> DistanceMatrix<T> {
 ///  }
 ///  ```
 pub struct Coordinates2D<'a, T: Num> {
-    coords: HashMap<&'a str, (T, T)>
+    coords: HashMap<&'a str, (T, T)>,
+    metric: DistanceMetric
 }
 
 impl<'a, T: Num> Coordinates2D<'a, T> {
-    /// Creates the structure representing coordinates of type {'city name': (x, y), ...}
+    /// Creates the structure representing coordinates of type {'city name': (x, y), ...},
+    /// using the `Euc2D` distance metric. Use [`Self::with_metric`] to
+    /// select a different one.
     pub fn new(coords: HashMap<&'a str, (T, T)>) -> Self {
+        Self::with_metric(coords, DistanceMetric::Euc2D)
+    }
+
+    /// Creates the structure representing coordinates of type {'city name': (x, y), ...},
+    /// interpreted with the given [`DistanceMetric`].
+    pub fn with_metric(coords: HashMap<&'a str, (T, T)>, metric: DistanceMetric) -> Self {
+        assert!(coords.len() >= 3, "there must be at least 3 cities");
+        Coordinates2D { coords, metric }
+    }
+
+    /// Returns a list of city names in the order of the TSP tour. You can adjust solver iterations with the runs parameter.
+    pub fn solve(&self, runs: usize) -> Vec<&'a str> {
+        let (param, problem) = self.to_problem(runs);
+        self.tour_from_indices(elkai_solve_problem(&param, &problem))
+    }
+}
+
+impl<'a, T: Num + Copy + num_traits::ToPrimitive> Coordinates2D<'a, T> {
+    /// Returns the TSP tour together with its total closed-loop length
+    /// (using the instance's [`DistanceMetric`]) and the number of runs
+    /// used, so callers don't have to re-implement cost computation
+    /// themselves. You can adjust solver iterations with the runs parameter.
+    pub fn solve_with_cost(&self, runs: usize) -> Solution<&'a str, f64> {
+        let tour = self.solve(runs);
+
+        let point = |city: &str| {
+            let (x, y) = self.coords[city];
+            (x.to_f64().unwrap(), y.to_f64().unwrap())
+        };
+
+        let mut length = (1..tour.len()).map(|i| self.metric.distance(point(tour[i - 1]), point(tour[i]))).sum::<f64>();
+        length += self.metric.distance(point(tour[tour.len() - 1]), point(tour[0]));
+        Solution { tour, length, runs }
+    }
+
+    /// Solves large instances by partitioning the points into a grid of
+    /// cells sized around `cell_capacity`, solving each cell with
+    /// [`solve`](Self::solve), and stitching the per-cell tours together in
+    /// snake order with a bounded boundary refinement pass. You can adjust
+    /// solver iterations with the runs parameter.
+    pub fn solve_partitioned(&self, cell_capacity: usize, runs: usize) -> Vec<&'a str> {
+        assert!(cell_capacity >= 1, "cell_capacity must be a positive integer");
+        assert!(runs >= 1, "runs must be a positive integer");
+
+        let keys: Vec<&&str> = self.coords.keys().collect();
+        let points: Vec<(f64, f64)> = keys.iter().map(|k| {
+            let (x, y) = self.coords[***k];
+            (x.to_f64().unwrap(), y.to_f64().unwrap())
+        }).collect();
+
+        let (min_x, max_x) = points.iter().map(|p| p.0)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| (lo.min(x), hi.max(x)));
+        let (min_y, max_y) = points.iter().map(|p| p.1)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), y| (lo.min(y), hi.max(y)));
+
+        let cells_target = (points.len() as f64 / cell_capacity as f64).max(1.0);
+        let grid_side = (cells_target.sqrt().ceil() as usize).max(1);
+        let cell_w = ((max_x - min_x) / grid_side as f64).max(f64::EPSILON);
+        let cell_h = ((max_y - min_y) / grid_side as f64).max(f64::EPSILON);
+
+        let mut cells: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (idx, (x, y)) in points.iter().enumerate() {
+            let cx = (((x - min_x) / cell_w) as usize).min(grid_side - 1);
+            let cy = (((y - min_y) / cell_h) as usize).min(grid_side - 1);
+            cells.entry((cx, cy)).or_default().push(idx);
+        }
+
+        let mut tour: Vec<&'a str> = Vec::with_capacity(points.len());
+        let mut segments: Vec<(usize, usize)> = Vec::new();
+        for cy in 0..grid_side {
+            let row: Vec<usize> = if cy % 2 == 0 { (0..grid_side).collect() } else { (0..grid_side).rev().collect() };
+            for cx in row {
+                let Some(members) = cells.get(&(cx, cy)) else { continue };
+                let start = tour.len();
+                if members.len() < 3 {
+                    tour.extend(members.iter().map(|&idx| **keys[idx]));
+                } else {
+                    let cell_coords: HashMap<&'a str, (T, T)> = members.iter()
+                        .map(|&idx| (**keys[idx], self.coords[**keys[idx]])).collect();
+                    tour.extend(Coordinates2D::with_metric(cell_coords, self.metric).solve(runs));
+                }
+                segments.push((start, tour.len() - 1));
+            }
+        }
+
+        let mut boundary_positions: Vec<usize> = Vec::new();
+        for &(start, end) in &segments {
+            boundary_positions.push(start);
+            if end != start {
+                boundary_positions.push(end);
+            }
+        }
+        if boundary_positions.len() >= 3 {
+            let boundary_coords: HashMap<&'a str, (T, T)> = boundary_positions.iter()
+                .map(|&pos| (tour[pos], self.coords[tour[pos]])).collect();
+            let refined = Coordinates2D::with_metric(boundary_coords, self.metric).solve(runs);
+            for (&pos, city) in boundary_positions.iter().zip(refined) {
+                tour[pos] = city;
+            }
+        }
+
+        tour
+    }
+}
+
+/// Converts a coordinate in decimal degrees into the `DDD.MM` form LKH's
+/// `GEO` edge-weight type expects, where the integer part is whole degrees
+/// and the fractional part encodes arc-minutes (so that LKH's own
+/// `deg + 5 * min / 3` great-circle conversion reconstructs the original
+/// angle).
+fn to_tsplib_geo(decimal_degrees: f64) -> f64 {
+    let deg = decimal_degrees.trunc();
+    let min = (decimal_degrees - deg) * 60.0;
+    deg + min / 100.0
+}
+
+/// Converts a coordinate already in the TSPLIB `GEO` `DDD.MM` wire format
+/// back into decimal degrees, the inverse of [`to_tsplib_geo`]. Needed when
+/// reading raw `NODE_COORD_SECTION` tokens from a GEO instance, which are
+/// already in this packed form rather than plain decimal degrees.
+fn from_tsplib_geo(tsplib_geo: f64) -> f64 {
+    let deg = tsplib_geo.trunc();
+    let min = tsplib_geo - deg;
+    deg + 5.0 * min / 3.0
+}
+
+/// A structure representing geographic coordinates of type
+/// {'city name': (latitude, longitude), ...} in decimal degrees, solved
+/// using the TSPLIB `GEO` great-circle distance convention.
+/// ## Example usage
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use elkai_rs::GeoCoordinates;
+///
+/// fn main() {
+///     let cities = GeoCoordinates::new(HashMap::from_iter([
+///         ("city1", (52.52, 13.405)),
+///         ("city2", (48.8566, 2.3522)),
+///         ("city3", (41.9028, 12.4964))
+///     ]));
+///     println!("{:?}", cities.solve(10));
+/// }
+/// ```
+pub struct GeoCoordinates<'a> {
+    coords: HashMap<&'a str, (f64, f64)>
+}
+
+impl<'a> GeoCoordinates<'a> {
+    /// Creates the structure representing geographic coordinates of type
+    /// {'city name': (latitude, longitude), ...} in decimal degrees.
+    pub fn new(coords: HashMap<&'a str, (f64, f64)>) -> Self {
         assert!(coords.len() >= 3, "there must be at least 3 cities");
-        Coordinates2D { coords }
+        GeoCoordinates { coords }
     }
 
     /// Returns a list of city names in the order of the TSP tour. You can adjust solver iterations with the runs parameter.
     pub fn solve(&self, runs: usize) -> Vec<&'a str> {
         assert!(runs >= 1, "runs must be a positive integer");
         let keys: Vec<&&str> = self.coords.keys().collect();
-        
+
         let keys_to_numbers: HashMap<&&&str, usize> = HashMap::from_iter(keys.iter().enumerate()
             .map(|(i, k)| (k, i + 1)));
         let numbers_to_keys: HashMap<usize, &&&str> = HashMap::from_iter(keys.iter().enumerate());
 
         let dimension = keys.len();
         let param = format!("RUNS = {runs}\nPROBLEM_FILE = :stdin:\n\0");
-        let mut problem = format!("TYPE : TSP\nDIMENSION : {dimension}\nEDGE_WEIGHT_TYPE : EUC_2D\nNODE_COORD_SECTION\n");
+        let mut problem = format!("TYPE : TSP\nDIMENSION : {dimension}\nEDGE_WEIGHT_TYPE : GEO\nNODE_COORD_SECTION\n");
         for (key, num) in keys_to_numbers.iter() {
-            let (x1, x2) = &self.coords[***key];
-            problem.push_str(&format!("{num} {x1} {x2}\n"));
+            let (lat, lon) = self.coords[***key];
+            problem.push_str(&format!("{num} {} {}\n", to_tsplib_geo(lat), to_tsplib_geo(lon)));
         }
         problem.push('\0');
 
@@ -201,10 +406,434 @@ impl<'a, T: Num> Coordinates2D<'a, T> {
     }
 }
 
+/// A structure representing 3D coordinates of type {'city name': (x, y, z), ...},
+/// solved using the TSPLIB `EUC_3D` edge-weight type.
+/// ## Example usage
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use elkai_rs::Coordinates3D;
+///
+/// fn main() {
+///     let cities = Coordinates3D::new(HashMap::from_iter([
+///         ("city1", (0.0, 0.0, 0.0)),
+///         ("city2", (0.0, 4.0, 0.0)),
+///         ("city3", (5.0, 0.0, 3.0))
+///     ]));
+///     println!("{:?}", cities.solve(10));
+/// }
+/// ```
+pub struct Coordinates3D<'a, T: Num> {
+    coords: HashMap<&'a str, (T, T, T)>
+}
+
+impl<'a, T: Num> Coordinates3D<'a, T> {
+    /// Creates the structure representing coordinates of type {'city name': (x, y, z), ...}
+    pub fn new(coords: HashMap<&'a str, (T, T, T)>) -> Self {
+        assert!(coords.len() >= 3, "there must be at least 3 cities");
+        Coordinates3D { coords }
+    }
+
+    /// Returns a list of city names in the order of the TSP tour. You can adjust solver iterations with the runs parameter.
+    pub fn solve(&self, runs: usize) -> Vec<&'a str> {
+        assert!(runs >= 1, "runs must be a positive integer");
+        let keys: Vec<&&str> = self.coords.keys().collect();
+
+        let keys_to_numbers: HashMap<&&&str, usize> = HashMap::from_iter(keys.iter().enumerate()
+            .map(|(i, k)| (k, i + 1)));
+        let numbers_to_keys: HashMap<usize, &&&str> = HashMap::from_iter(keys.iter().enumerate());
+
+        let dimension = keys.len();
+        let param = format!("RUNS = {runs}\nPROBLEM_FILE = :stdin:\n\0");
+        let mut problem = format!("TYPE : TSP\nDIMENSION : {dimension}\nEDGE_WEIGHT_TYPE : EUC_3D\nNODE_COORD_SECTION\n");
+        for (key, num) in keys_to_numbers.iter() {
+            let (x1, x2, x3) = &self.coords[***key];
+            problem.push_str(&format!("{num} {x1} {x2} {x3}\n"));
+        }
+        problem.push('\0');
+
+        elkai_solve_problem(&param, &problem).into_iter().map(|num| {
+            **numbers_to_keys[&num]
+        }).collect()
+    }
+}
+
+/// Precision multiplier used to convert floating point distances into
+/// integer edge weights before running Floyd-Warshall and handing the
+/// result off to [`DistanceMatrix::solve`], which requires exact integer
+/// reduction to stay correct.
+const HUB_DISTANCE_SCALE: f64 = 1000.0;
+
+/// A single stop along a [`HubCoordinates2D`] tour: either one of the
+/// required cities, or a hub waypoint that was used as a free relay
+/// between two cities.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HubWaypoint<'a> {
+    City(&'a str),
+    Hub((f64, f64)),
+}
+
+/// A structure representing required cities of type {'city name': (x, y), ...}
+/// plus optional hub waypoints of type (x, y) that may be used as free
+/// relays, with any edge touching a required city scaled by `alpha`.
+/// ## Example usage
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use elkai_rs::HubCoordinates2D;
+///
+/// fn main() {
+///     let cities = HubCoordinates2D::new(HashMap::from_iter([
+///         ("city1", (0.0, 0.0)),
+///         ("city2", (0.0, 4.0)),
+///         ("city3", (5.0, 0.0))
+///     ]), vec![(2.0, 2.0)], 2.0);
+///     println!("{:?}", cities.solve(10));
+/// }
+/// ```
+pub struct HubCoordinates2D<'a> {
+    cities: HashMap<&'a str, (f64, f64)>,
+    hubs: Vec<(f64, f64)>,
+    alpha: f64
+}
+
+impl<'a> HubCoordinates2D<'a> {
+    /// Creates the structure representing the required cities, the
+    /// optional relay hubs, and the penalty multiplier `alpha` applied to
+    /// any edge that touches a required city.
+    pub fn new(cities: HashMap<&'a str, (f64, f64)>, hubs: Vec<(f64, f64)>, alpha: f64) -> Self {
+        assert!(cities.len() >= 3, "there must be at least 3 cities");
+        HubCoordinates2D { cities, hubs, alpha }
+    }
+
+    /// Returns the TSP tour over the required cities, with any hub
+    /// waypoints used spliced back in between the cities they connect.
+    /// You can adjust solver iterations with the runs parameter.
+    pub fn solve(&self, runs: usize) -> Vec<HubWaypoint<'a>> {
+        assert!(runs >= 1, "runs must be a positive integer");
+
+        let keys: Vec<&&str> = self.cities.keys().collect();
+        let n = keys.len();
+        let m = self.hubs.len();
+        let total = n + m;
+
+        let points: Vec<(f64, f64)> = keys.iter().map(|k| self.cities[***k])
+            .chain(self.hubs.iter().copied()).collect();
+
+        let mut cost = vec![vec![0i64; total]; total];
+        let mut next = vec![vec![usize::MAX; total]; total];
+        for i in 0..total {
+            for j in 0..total {
+                if i == j { continue; }
+                let (x1, y1) = points[i];
+                let (x2, y2) = points[j];
+                let dist = ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt();
+                let scale = if i < n || j < n { self.alpha } else { 1.0 };
+                cost[i][j] = (dist * scale * HUB_DISTANCE_SCALE).round() as i64;
+                next[i][j] = j;
+            }
+        }
+
+        for k in 0..total {
+            for i in 0..total {
+                for j in 0..total {
+                    let through = cost[i][k] + cost[k][j];
+                    if through < cost[i][j] {
+                        cost[i][j] = through;
+                        next[i][j] = next[i][k];
+                    }
+                }
+            }
+        }
+
+        let city_distances: Vec<Vec<i64>> = (0..n).map(|i| (0..n).map(|j| cost[i][j]).collect()).collect();
+        let order = DistanceMatrix::new(city_distances).solve(runs);
+
+        let mut tour = Vec::new();
+        for w in 0..order.len() {
+            let i = order[w];
+            let j = order[(w + 1) % order.len()];
+            tour.push(HubWaypoint::City(**keys[i]));
+            let mut cur = i;
+            while cur != j {
+                cur = next[cur][j];
+                if cur >= n {
+                    tour.push(HubWaypoint::Hub(points[cur]));
+                }
+            }
+        }
+        tour
+    }
+}
+
+fn tsplib_header<'a>(content: &'a str, key: &str) -> Option<&'a str> {
+    content.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix(key)?.trim_start();
+        rest.strip_prefix(':').map(str::trim)
+    })
+}
+
+fn tsplib_section_values<'a>(content: &'a str, section: &str) -> Vec<&'a str> {
+    let start = content.find(section).expect("missing TSPLIB section") + section.len();
+    content[start..].split_whitespace().take_while(|token| *token != "EOF").collect()
+}
+
+fn tsplib_leak_node_name(token: &str) -> &'static str {
+    Box::leak(token.parse::<usize>().expect("invalid NODE_COORD_SECTION index").to_string().into_boxed_str())
+}
+
+/// The result of parsing a TSPLIB instance: 2D, geographic, or 3D city
+/// coordinates for `NODE_COORD`-based instances (depending on
+/// `EDGE_WEIGHT_TYPE`), or an explicit distance matrix for `EXPLICIT` ones.
+pub enum TsplibInstance {
+    Coordinates(Coordinates2D<'static, f64>),
+    Geo(GeoCoordinates<'static>),
+    Coordinates3D(Coordinates3D<'static, f64>),
+    Distances(DistanceMatrix<i64>)
+}
+
+impl TsplibInstance {
+    /// Parses a TSPLIB-formatted instance from its full file contents,
+    /// reading the `DIMENSION`, `EDGE_WEIGHT_TYPE`, and
+    /// `EDGE_WEIGHT_FORMAT` headers together with the `NODE_COORD_SECTION`
+    /// / `EDGE_WEIGHT_SECTION` body. The `TYPE` header is not read.
+    /// `EXPLICIT` instances take a `FULL_MATRIX` as given and reconstruct
+    /// the full symmetric matrix from `UPPER_ROW`, `LOWER_DIAG_ROW`, or
+    /// `UPPER_DIAG_ROW` triangular data;
+    /// `EUC_2D`/`CEIL_2D`/`ATT` become [`TsplibInstance::Coordinates`],
+    /// `GEO` becomes [`TsplibInstance::Geo`], and `EUC_3D` becomes
+    /// [`TsplibInstance::Coordinates3D`]. Panics on any other
+    /// `EDGE_WEIGHT_TYPE`.
+    ///
+    /// Node names are synthesized from the instance's 1-based node indices
+    /// and leaked for the lifetime of the program, mirroring the borrowed
+    /// `&'static str` keys [`Coordinates2D`] expects.
+    pub fn from_tsplib(content: &str) -> Self {
+        let dimension: usize = tsplib_header(content, "DIMENSION")
+            .expect("missing DIMENSION header").parse().expect("invalid DIMENSION header");
+        let edge_weight_type = tsplib_header(content, "EDGE_WEIGHT_TYPE").unwrap_or("EUC_2D");
+
+        match edge_weight_type {
+            "EXPLICIT" => {
+                let format = tsplib_header(content, "EDGE_WEIGHT_FORMAT").unwrap_or("FULL_MATRIX");
+                let values: Vec<i64> = tsplib_section_values(content, "EDGE_WEIGHT_SECTION").into_iter()
+                    .map(|t| t.parse().expect("invalid EDGE_WEIGHT_SECTION entry")).collect();
+
+                let mut distances = vec![vec![0i64; dimension]; dimension];
+                let mut it = values.into_iter();
+                match format {
+                    "FULL_MATRIX" => for i in 0..dimension {
+                        for j in 0..dimension {
+                            distances[i][j] = it.next().expect("truncated EDGE_WEIGHT_SECTION");
+                        }
+                    },
+                    "UPPER_ROW" => for i in 0..dimension {
+                        for j in (i + 1)..dimension {
+                            let w = it.next().expect("truncated EDGE_WEIGHT_SECTION");
+                            distances[i][j] = w;
+                            distances[j][i] = w;
+                        }
+                    },
+                    "LOWER_DIAG_ROW" => for i in 0..dimension {
+                        for j in 0..=i {
+                            let w = it.next().expect("truncated EDGE_WEIGHT_SECTION");
+                            distances[i][j] = w;
+                            distances[j][i] = w;
+                        }
+                    },
+                    "UPPER_DIAG_ROW" => for i in 0..dimension {
+                        for j in i..dimension {
+                            let w = it.next().expect("truncated EDGE_WEIGHT_SECTION");
+                            distances[i][j] = w;
+                            distances[j][i] = w;
+                        }
+                    },
+                    other => panic!("unsupported EDGE_WEIGHT_FORMAT: {other}")
+                }
+                TsplibInstance::Distances(DistanceMatrix::new(distances))
+            },
+            "GEO" => {
+                let tokens = tsplib_section_values(content, "NODE_COORD_SECTION");
+                let coords = tokens.chunks(3).map(|chunk| {
+                    let name = tsplib_leak_node_name(chunk[0]);
+                    let lat: f64 = chunk[1].parse().expect("invalid NODE_COORD_SECTION entry");
+                    let lon: f64 = chunk[2].parse().expect("invalid NODE_COORD_SECTION entry");
+                    (name, (from_tsplib_geo(lat), from_tsplib_geo(lon)))
+                }).collect();
+                TsplibInstance::Geo(GeoCoordinates::new(coords))
+            },
+            "EUC_3D" => {
+                let tokens = tsplib_section_values(content, "NODE_COORD_SECTION");
+                let coords = tokens.chunks(4).map(|chunk| {
+                    let name = tsplib_leak_node_name(chunk[0]);
+                    let x: f64 = chunk[1].parse().expect("invalid NODE_COORD_SECTION entry");
+                    let y: f64 = chunk[2].parse().expect("invalid NODE_COORD_SECTION entry");
+                    let z: f64 = chunk[3].parse().expect("invalid NODE_COORD_SECTION entry");
+                    (name, (x, y, z))
+                }).collect();
+                TsplibInstance::Coordinates3D(Coordinates3D::new(coords))
+            },
+            "EUC_2D" | "CEIL_2D" | "ATT" => {
+                let tokens = tsplib_section_values(content, "NODE_COORD_SECTION");
+                let coords = tokens.chunks(3).map(|chunk| {
+                    let name = tsplib_leak_node_name(chunk[0]);
+                    let x: f64 = chunk[1].parse().expect("invalid NODE_COORD_SECTION entry");
+                    let y: f64 = chunk[2].parse().expect("invalid NODE_COORD_SECTION entry");
+                    (name, (x, y))
+                }).collect();
+                let metric = match edge_weight_type {
+                    "CEIL_2D" => DistanceMetric::Ceil2D,
+                    "ATT" => DistanceMetric::Att,
+                    _ => DistanceMetric::Euc2D
+                };
+                TsplibInstance::Coordinates(Coordinates2D::with_metric(coords, metric))
+            },
+            other => panic!("unsupported EDGE_WEIGHT_TYPE: {other}")
+        }
+    }
+
+    /// Reads a TSPLIB file from disk and parses it with [`Self::from_tsplib`].
+    pub fn from_tsplib_file(path: impl AsRef<std::path::Path>) -> Self {
+        let content = std::fs::read_to_string(path).expect("failed to read TSPLIB file");
+        Self::from_tsplib(&content)
+    }
+}
+
+/// Entry point used by the `elkai_worker` helper binary to run a single
+/// solve in its own process, where its lock of `ELKAI_MUTEX` is not shared
+/// with any other solve. Not part of the
+/// crate's public contract; use [`solve_batch`] instead.
+#[doc(hidden)]
+pub fn __solve_raw(param: &str, problem: &str) -> Vec<usize> {
+    elkai_solve_problem(param, problem)
+}
+
+/// A TSP problem that can be serialized into the `(parameters, problem)`
+/// TSPLIB pair LKH expects, with its solved tour recovered from the raw
+/// list of 0-based node indices LKH returns. Implemented by
+/// [`DistanceMatrix`] and [`Coordinates2D`], and used by [`solve_batch`]
+/// to dispatch problems to worker processes.
+pub trait BatchSolvable {
+    type Tour;
+    #[doc(hidden)]
+    fn to_problem(&self, runs: usize) -> (String, String);
+    #[doc(hidden)]
+    fn tour_from_indices(&self, indices: Vec<usize>) -> Self::Tour;
+}
+
+impl<T: Num> BatchSolvable for DistanceMatrix<T> {
+    type Tour = Vec<usize>;
+
+    fn to_problem(&self, runs: usize) -> (String, String) {
+        assert!(runs >= 1, "runs must be a positive integer");
+        let dimension = self.distances.len();
+        assert!(dimension >= 3, "dimension must be at least 3");
+        let param = format!("RUNS = {runs}\nPROBLEM_FILE = :stdin:\n\0");
+        let problem_type = if is_symmetric_matrix(&self.distances) {"TSP"} else {"ATSP"};
+        let mut problem = format!("TYPE : {problem_type}\nDIMENSION : {dimension}\nEDGE_WEIGHT_TYPE : EXPLICIT\nEDGE_WEIGHT_FORMAT : FULL_MATRIX\nEDGE_WEIGHT_SECTION\n");
+        for row in &self.distances {
+            problem.push_str(&row.iter().map(T::to_string).collect::<Vec<_>>().join(" "));
+            problem.push('\n');
+        }
+        problem.push('\0');
+        (param, problem)
+    }
+
+    fn tour_from_indices(&self, indices: Vec<usize>) -> Vec<usize> {
+        indices
+    }
+}
+
+impl<'a, T: Num> BatchSolvable for Coordinates2D<'a, T> {
+    type Tour = Vec<&'a str>;
+
+    fn to_problem(&self, runs: usize) -> (String, String) {
+        assert!(runs >= 1, "runs must be a positive integer");
+        let mut keys: Vec<&&str> = self.coords.keys().collect();
+        keys.sort();
+
+        let dimension = keys.len();
+        let param = format!("RUNS = {runs}\nPROBLEM_FILE = :stdin:\n\0");
+        let metric = self.metric.tsplib_name();
+        let mut problem = format!("TYPE : TSP\nDIMENSION : {dimension}\nEDGE_WEIGHT_TYPE : {metric}\nNODE_COORD_SECTION\n");
+        for (i, key) in keys.iter().enumerate() {
+            let (x1, x2) = &self.coords[***key];
+            problem.push_str(&format!("{} {x1} {x2}\n", i + 1));
+        }
+        problem.push('\0');
+        (param, problem)
+    }
+
+    fn tour_from_indices(&self, indices: Vec<usize>) -> Vec<&'a str> {
+        let mut keys: Vec<&&str> = self.coords.keys().collect();
+        keys.sort();
+        indices.into_iter().map(|num| **keys[num]).collect()
+    }
+}
+
+/// Locates the `elkai_worker` helper binary shipped alongside this crate.
+/// Cargo places `[[bin]]` targets directly under `target/<profile>/`, but
+/// test harness binaries run from `target/<profile>/deps/`, so this checks
+/// the executable's own directory and then each ancestor up to and
+/// including a directory named `debug` or `release`, falling back to whatever
+/// `elkai_worker` resolves to on `PATH`.
+fn worker_binary_path() -> std::path::PathBuf {
+    let name = if cfg!(windows) { "elkai_worker.exe" } else { "elkai_worker" };
+    std::env::current_exe().ok()
+        .and_then(|exe| {
+            let mut dir = exe.parent();
+            while let Some(d) = dir {
+                let candidate = d.join(name);
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+                if d.file_name().is_some_and(|n| n == "debug" || n == "release") {
+                    break;
+                }
+                dir = d.parent();
+            }
+            None
+        })
+        .unwrap_or_else(|| std::path::PathBuf::from(name))
+}
+
+/// Solves many independent problems in parallel, each dispatched to its own
+/// `elkai_worker` process to bypass `ELKAI_MUTEX`. Tours are returned in
+/// input order. You can adjust solver iterations with the runs parameter.
+pub fn solve_batch<P: BatchSolvable>(problems: &[P], runs: usize) -> Vec<P::Tour> {
+    use std::io::Write;
+
+    let handles: Vec<_> = problems.iter().map(|problem| {
+        let (param, problem) = problem.to_problem(runs);
+        std::thread::spawn(move || {
+            let mut child = std::process::Command::new(worker_binary_path())
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::piped())
+                .spawn()
+                .expect("failed to spawn elkai_worker process");
+            child.stdin.take().unwrap().write_all(format!("{param}{problem}").as_bytes())
+                .expect("failed to write to elkai_worker process");
+            let output = child.wait_with_output().expect("elkai_worker process failed");
+            if !output.status.success() {
+                panic!("elkai_worker process failed: {}", String::from_utf8_lossy(&output.stderr));
+            }
+            String::from_utf8(output.stdout).expect("elkai_worker produced non-UTF8 output")
+                .split_whitespace().map(|n| n.parse::<usize>().expect("invalid elkai_worker output"))
+                .collect::<Vec<usize>>()
+        })
+    }).collect();
+
+    problems.iter().zip(handles).map(|(problem, handle)| {
+        let indices = handle.join().expect("elkai_worker thread panicked");
+        problem.tour_from_indices(indices)
+    }).collect()
+}
+
 #[cfg(test)]
 mod test {
-    use std::{collections::HashMap, io::Read};
-    use crate::{elkai_solve_problem, Coordinates2D, DistanceMatrix};
+    use std::collections::HashMap;
+    use crate::{elkai_solve_problem, solve_batch, Coordinates2D, Coordinates3D, DistanceMatrix, DistanceMetric, GeoCoordinates, HubCoordinates2D, TsplibInstance};
 
     #[test]
     fn elkai_str() {
@@ -246,23 +875,11 @@ mod test {
 
     #[test]
     fn pr2392() {
-        use text_io::scan;
-
-        let mut s = String::new();
-        std::fs::File::open("LKH-3.0.8/pr2392.tsp").unwrap().read_to_string(&mut s).unwrap();
-        let start = s.find("NODE_COORD_SECTION").unwrap() + "NODE_COORD_SECTION".len();
-        let end = s.rfind("EOF").unwrap();
-
-        let (mut k, mut v) = (vec![], vec![]);
-        for line in s[start..end].trim().lines() {
-            let (idx, x, y): (usize, f64, f64);
-            scan!(line.bytes() => "{} {} {}", idx, x, y);
-            k.push(idx.to_string());
-            v.push((x, y));
-        }
-
-        let coords: HashMap<&str, (f64, f64)> = HashMap::from_iter(k.iter().zip(v).map(|(k, v)| (k.as_str(), v)));
-        let s = Coordinates2D::new(coords.clone());
+        let s = match TsplibInstance::from_tsplib_file("LKH-3.0.8/pr2392.tsp") {
+            TsplibInstance::Coordinates(s) => s,
+            _ => panic!("expected a NODE_COORD-based instance"),
+        };
+        let coords: HashMap<&str, (f64, f64)> = s.coords.clone();
         let solution = s.solve(10);
         println!("{:?}", solution);
         println!("{:?}", coords_result(&coords, &solution))
@@ -278,19 +895,130 @@ mod test {
 
     #[test]
     fn whizzkids96() {
-        let mut s = String::new();
-        std::fs::File::open("LKH-3.0.8/whizzkids96.atsp").unwrap().read_to_string(&mut s).unwrap();
-        let start = s.find("EDGE_WEIGHT_SECTION").unwrap() + "EDGE_WEIGHT_SECTION".len();
-        let distances = s[start..].trim().lines().map(|line| line.split(' ').filter_map(|e| {
-            let e = e.trim();
-            match e.is_empty() {
-                true => None,
-                false => Some(e.parse::<usize>().unwrap()),
-            }
-        }).collect::<Vec<_>>()).collect::<Vec<_>>();
-        let s = DistanceMatrix::new(distances.clone());
+        let s = match TsplibInstance::from_tsplib_file("LKH-3.0.8/whizzkids96.atsp") {
+            TsplibInstance::Distances(s) => s,
+            _ => panic!("expected an EXPLICIT instance"),
+        };
+        let distances = s.distances.clone();
         let solution = s.solve(10);
         println!("{:?}", solution);
         println!("{:?}", distances_result(&distances, &solution));
     }
+
+    #[test]
+    fn geo_coordinates() {
+        let s = GeoCoordinates::new(HashMap::from_iter([
+            ("berlin", (52.52, 13.405)),
+            ("paris", (48.8566, 2.3522)),
+            ("rome", (41.9028, 12.4964))
+        ]));
+        println!("{:?}", s.solve(10));
+    }
+
+    #[test]
+    fn coordinates3d() {
+        let s = Coordinates3D::new(HashMap::from_iter([
+            ("city1", (0.0, 0.0, 0.0)),
+            ("city2", (0.0, 4.0, 0.0)),
+            ("city3", (5.0, 0.0, 3.0))
+        ]));
+        println!("{:?}", s.solve(10));
+    }
+
+    #[test]
+    fn from_tsplib_geo_decodes_ddd_mm() {
+        // "16.47" is LKH's packed DDD.MM wire format for 16 degrees, 47
+        // minutes, i.e. 16 + 5.0 * 0.47 / 3.0 decimal degrees.
+        let content = "TYPE : TSP\nDIMENSION : 3\nEDGE_WEIGHT_TYPE : GEO\nNODE_COORD_SECTION\n1 16.47 28.20\n2 48.47 2.47\n3 41.09 12.28\nEOF\n";
+        let s = match TsplibInstance::from_tsplib(content) {
+            TsplibInstance::Geo(s) => s,
+            _ => panic!("expected a GEO instance"),
+        };
+        let (lat, lon) = s.coords["1"];
+        assert!((lat - (16.0 + 5.0 * 0.47 / 3.0)).abs() < 1e-9);
+        assert!((lon - (28.0 + 5.0 * 0.20 / 3.0)).abs() < 1e-9);
+        println!("{:?}", s.solve(10));
+    }
+
+    #[test]
+    fn solve_batch_dis_mat() {
+        let problems = vec![
+            DistanceMatrix::new(vec![
+                vec![0, 4, 0],
+                vec![0, 0, 5],
+                vec![0, 0, 0]
+            ]),
+            DistanceMatrix::new(vec![
+                vec![0, 1, 2],
+                vec![1, 0, 3],
+                vec![2, 3, 0]
+            ])
+        ];
+        println!("{:?}", solve_batch(&problems, 10));
+    }
+
+    #[test]
+    fn dis_mat_solve_with_cost() {
+        let s = DistanceMatrix::new(vec![
+            vec![0, 4, 0],
+            vec![0, 0, 5],
+            vec![0, 0, 0]
+        ]);
+        println!("{:?}", s.solve_with_cost(10));
+    }
+
+    #[test]
+    fn coordinates2d_solve_with_cost() {
+        let s = Coordinates2D::new(HashMap::from_iter([
+            ("city1", (0.0, 0.0)),
+            ("city2", (0.0, 4.0)),
+            ("city3", (5.0, 0.0))
+        ]));
+        println!("{:?}", s.solve_with_cost(10));
+    }
+
+    #[test]
+    fn coordinates2d_solve_with_cost_att() {
+        let s = Coordinates2D::with_metric(HashMap::from_iter([
+            ("city1", (0.0, 0.0)),
+            ("city2", (0.0, 4.0)),
+            ("city3", (5.0, 0.0))
+        ]), DistanceMetric::Att);
+        let solution = s.solve_with_cost(10);
+        assert_ne!(solution.length, 9.0);
+        println!("{:?}", solution);
+    }
+
+    #[test]
+    fn solve_partitioned() {
+        let s = Coordinates2D::new(HashMap::from_iter([
+            ("city1", (0.0, 0.0)),
+            ("city2", (0.0, 4.0)),
+            ("city3", (5.0, 0.0)),
+            ("city4", (5.0, 4.0)),
+            ("city5", (10.0, 10.0)),
+            ("city6", (12.0, 8.0))
+        ]));
+        println!("{:?}", s.solve_partitioned(3, 10));
+    }
+
+    #[test]
+    fn hub_coordinates2d() {
+        let s = HubCoordinates2D::new(HashMap::from_iter([
+            ("city1", (0.0, 0.0)),
+            ("city2", (0.0, 4.0)),
+            ("city3", (5.0, 0.0))
+        ]), vec![(2.0, 2.0)], 2.0);
+        println!("{:?}", s.solve(10));
+    }
+
+    #[test]
+    fn from_tsplib_euc_3d() {
+        let content = "TYPE : TSP\nDIMENSION : 3\nEDGE_WEIGHT_TYPE : EUC_3D\nNODE_COORD_SECTION\n1 0.0 0.0 0.0\n2 0.0 4.0 0.0\n3 5.0 0.0 3.0\nEOF\n";
+        let s = match TsplibInstance::from_tsplib(content) {
+            TsplibInstance::Coordinates3D(s) => s,
+            _ => panic!("expected an EUC_3D instance"),
+        };
+        println!("{:?}", s.solve(10));
+    }
 }
\ No newline at end of file